@@ -0,0 +1,208 @@
+//! Incremental CSC (compressed sparse column) construction.
+//!
+//! Building a matrix by handing a `Vec` of triplets to
+//! `try_new_from_triplets` sorts and deduplicates internally -- wasted work
+//! on the hot canonicalization path when the caller already produces
+//! entries in column-major order, as
+//! `identity_kron2` demonstrates by writing `col_ptrs` directly. `CscBuilder`
+//! generalizes that direct-write pattern: it accepts entries with columns in
+//! non-decreasing order and, within a column, rows in strictly increasing
+//! order, pushing straight into the CSC arrays with no sort or dedup.
+
+use std::fmt;
+
+use faer::sparse::{SparseColMat, SymbolicSparseColMat};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum CscBuilderError {
+    ColumnOutOfOrder { col: usize, last_col: usize },
+    RowOutOfOrder { row: u64, last_row: u64, col: usize },
+    ColumnOutOfBounds { col: usize, ncols: usize },
+    RowOutOfBounds { row: u64, nrows: usize },
+}
+
+impl fmt::Display for CscBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CscBuilderError::ColumnOutOfOrder { col, last_col } => write!(
+                f,
+                "CscBuilder: column {col} pushed after column {last_col}, columns must be non-decreasing"
+            ),
+            CscBuilderError::RowOutOfOrder { row, last_row, col } => write!(
+                f,
+                "CscBuilder: row {row} pushed after row {last_row} in column {col}, rows must be strictly increasing within a column"
+            ),
+            CscBuilderError::ColumnOutOfBounds { col, ncols } => write!(
+                f,
+                "CscBuilder: column {col} is out of bounds for a matrix with {ncols} columns"
+            ),
+            CscBuilderError::RowOutOfBounds { row, nrows } => write!(
+                f,
+                "CscBuilder: row {row} is out of bounds for a matrix with {nrows} rows"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CscBuilderError {}
+
+/// Builds a `SparseColMat<u64, f64>` column by column, with no sorting or
+/// deduplication. Columns must be pushed in non-decreasing order; within a
+/// column, rows must be strictly increasing.
+pub(crate) struct CscBuilder {
+    nrows: usize,
+    ncols: usize,
+    row_indices: Vec<u64>,
+    values: Vec<f64>,
+    col_ptrs: Vec<u64>,
+    last_col: usize,
+    last_row_in_col: Option<u64>,
+}
+
+impl CscBuilder {
+    pub(crate) fn new(nrows: usize, ncols: usize) -> Self {
+        Self::with_capacity(nrows, ncols, 0)
+    }
+
+    pub(crate) fn with_capacity(nrows: usize, ncols: usize, nnz: usize) -> Self {
+        let mut col_ptrs = Vec::with_capacity(ncols + 1);
+        col_ptrs.push(0);
+        CscBuilder {
+            nrows,
+            ncols,
+            row_indices: Vec::with_capacity(nnz),
+            values: Vec::with_capacity(nnz),
+            col_ptrs,
+            last_col: 0,
+            last_row_in_col: None,
+        }
+    }
+
+    /// Push a `(row, col, value)` entry. `col` must be `>=` the column of the
+    /// previous push; within a column, `row` must be strictly greater than
+    /// the row of the previous push to that same column.
+    pub(crate) fn push(&mut self, row: u64, col: usize, value: f64) -> Result<(), CscBuilderError> {
+        if col >= self.ncols {
+            return Err(CscBuilderError::ColumnOutOfBounds {
+                col,
+                ncols: self.ncols,
+            });
+        }
+        if row >= self.nrows as u64 {
+            return Err(CscBuilderError::RowOutOfBounds {
+                row,
+                nrows: self.nrows,
+            });
+        }
+        if col < self.last_col {
+            return Err(CscBuilderError::ColumnOutOfOrder {
+                col,
+                last_col: self.last_col,
+            });
+        }
+        if col > self.last_col {
+            for _ in self.last_col..col {
+                self.col_ptrs.push(self.row_indices.len() as u64);
+            }
+            self.last_col = col;
+            self.last_row_in_col = None;
+        }
+        if let Some(last_row) = self.last_row_in_col {
+            if row <= last_row {
+                return Err(CscBuilderError::RowOutOfOrder {
+                    row,
+                    last_row,
+                    col,
+                });
+            }
+        }
+        self.last_row_in_col = Some(row);
+        self.row_indices.push(row);
+        self.values.push(value);
+        Ok(())
+    }
+
+    /// Finish building, closing out any trailing empty columns.
+    pub(crate) fn finish(mut self) -> SparseColMat<u64, f64> {
+        for _ in self.last_col..self.ncols {
+            self.col_ptrs.push(self.row_indices.len() as u64);
+        }
+        SparseColMat::new(
+            SymbolicSparseColMat::new_checked(
+                self.nrows,
+                self.ncols,
+                self.col_ptrs,
+                None,
+                self.row_indices,
+            ),
+            self.values,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::faer_ext::to_triplets_iter;
+
+    #[test]
+    fn test_builds_matching_triplets() {
+        let mut builder = CscBuilder::new(3, 3);
+        builder.push(0, 0, 1.0).unwrap();
+        builder.push(2, 0, 2.0).unwrap();
+        builder.push(1, 2, 3.0).unwrap();
+        let result = builder.finish();
+
+        assert_eq!(result.nrows(), 3);
+        assert_eq!(result.ncols(), 3);
+        assert_eq!(
+            to_triplets_iter(&result).collect::<Vec<_>>(),
+            vec![(0, 0, 1.0), (2, 0, 2.0), (1, 2, 3.0)]
+        );
+    }
+
+    #[test]
+    fn test_rejects_column_out_of_order() {
+        let mut builder = CscBuilder::new(2, 2);
+        builder.push(0, 1, 1.0).unwrap();
+        assert_eq!(
+            builder.push(0, 0, 2.0),
+            Err(CscBuilderError::ColumnOutOfOrder {
+                col: 0,
+                last_col: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_rejects_row_out_of_order() {
+        let mut builder = CscBuilder::new(2, 2);
+        builder.push(1, 0, 1.0).unwrap();
+        assert_eq!(
+            builder.push(1, 0, 2.0),
+            Err(CscBuilderError::RowOutOfOrder {
+                row: 1,
+                last_row: 1,
+                col: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_rejects_column_out_of_bounds() {
+        let mut builder = CscBuilder::new(2, 2);
+        assert_eq!(
+            builder.push(0, 2, 1.0),
+            Err(CscBuilderError::ColumnOutOfBounds { col: 2, ncols: 2 })
+        );
+    }
+
+    #[test]
+    fn test_rejects_row_out_of_bounds() {
+        let mut builder = CscBuilder::new(2, 2);
+        assert_eq!(
+            builder.push(2, 0, 1.0),
+            Err(CscBuilderError::RowOutOfBounds { row: 2, nrows: 2 })
+        );
+    }
+}
@@ -5,22 +5,35 @@ use faer::{
     ComplexField, Conjugate, Index, SimpleEntity,
 };
 
+use crate::csc_builder::CscBuilder;
 use crate::SparseMatrix;
 
-/*
-pub fn reshape<I: Index, E: SimpleEntity>(A: SparseColMatRef<'_, I, E>,
-                                               (m, n): (I, I)) -> SparseColMat<I, E> {
-    //! Reshape A into (m,n) in Fortran (column-major) order.
-    let oldn: I = A.ncols();
-    let mut triplets: Vec<(I, I, E)> = Vec::with_capacity(A.compute_nnz()); // Check this is the
-                                                                            // write method
-    for oldi in 0..oldn {
-        for (oldj, v) in A.col_indices_of_row(oldi).zip(A.values_of_row(oldi)) {
-            triplets.push((oldj * oldn + oldi) % m, (oldj * oldn + oldi) / m, *v);
+/// Reshape A into (m, n) in Fortran (column-major) order.
+///
+/// Every stored entry at `(i, j)` in the `oldm x oldn` input has a linear
+/// column-major index `k = j * oldm + i`; the reshaped matrix places that
+/// same value at `(k % m, k / m)`.
+pub fn reshape(
+    A: SparseColMatRef<'_, u64, f64>,
+    (m, n): (u64, u64),
+) -> SparseColMat<u64, f64> {
+    let oldm = A.nrows() as u64;
+    let oldn = A.ncols() as u64;
+    assert_eq!(
+        m * n,
+        oldm * oldn,
+        "reshape: new shape ({m}, {n}) has a different number of entries than old shape ({oldm}, {oldn})"
+    );
+
+    let mut triplets: Vec<(u64, u64, f64)> = Vec::with_capacity(A.compute_nnz());
+    for oldj in 0..A.ncols() {
+        for (oldi, &v) in A.row_indices_of_col(oldj).zip(A.values_of_col(oldj)) {
+            let k = oldj as u64 * oldm + oldi as u64;
+            triplets.push((k % m, k / m, v));
         }
     }
-    SparseColMat::try_new_from_triplets(m, n, triplets).unwrap()
-} */
+    SparseColMat::try_new_from_triplets(m as usize, n as usize, &triplets).unwrap()
+}
 
 pub fn eye(n: u64) -> SparseColMat<u64, f64> {
     let n_usize = n.try_into().unwrap();
@@ -52,40 +65,68 @@ where
 
 pub fn select_rows(A: &SparseColMat<u64, f64>, rows: &[u64]) -> SparseColMat<u64, f64> {
     let csr = A.to_row_major().unwrap();
-    let mut triplets = Vec::new();
 
+    // Bucket entries by column while walking the selected rows in order, so
+    // that each bucket already holds its (new row, value) pairs in
+    // increasing new-row order; CscBuilder can then consume them directly
+    // without a sort/dedup pass.
+    let mut buckets: Vec<Vec<(u64, f64)>> = vec![Vec::new(); A.ncols()];
     for (i, &r) in rows.iter().enumerate() {
         for (j, &v) in csr
             .col_indices_of_row(r as usize)
             .zip(csr.values_of_row(r as usize))
         {
-            triplets.push((i as u64, j as u64, v));
+            buckets[j].push((i as u64, v));
+        }
+    }
+
+    let nnz: usize = buckets.iter().map(Vec::len).sum();
+    let mut builder = CscBuilder::with_capacity(rows.len(), A.ncols(), nnz);
+    for (j, bucket) in buckets.into_iter().enumerate() {
+        for (i, v) in bucket {
+            builder.push(i, j, v).unwrap();
         }
     }
-    SparseColMat::try_new_from_triplets(rows.len(), A.ncols(), &triplets).unwrap()
+    builder.finish()
 }
 
 pub(crate) fn identity_kron(reps: u64, lhs: SparseColMat<u64, f64>) -> SparseColMat<u64, f64> {
     if reps == 1 {
         lhs
     } else {
-        let mut triplets = Vec::with_capacity(lhs.compute_nnz() * reps as usize);
-        for rep in 0..reps {
-            for (r, c, d) in to_triplets_iter(&lhs) {
-                triplets.push((
-                    r + rep * lhs.nrows() as u64,
-                    c + rep * lhs.ncols() as u64,
-                    d,
-                ));
+        kron(&eye(reps), &lhs)
+    }
+}
+
+/// Standard Kronecker product `A (x) B`.
+///
+/// For each stored entry `(ia, ja, va)` of `A` and `(ib, jb, vb)` of `B`,
+/// emits `(ia * B.nrows() + ib, ja * B.ncols() + jb, va * vb)`, producing an
+/// `(A.nrows() * B.nrows()) x (A.ncols() * B.ncols())` matrix. Columns (and,
+/// within each column, rows) of A and B are walked in increasing order, so
+/// the output is already column-major and can be fed straight into a
+/// `CscBuilder`.
+pub fn kron(A: &SparseColMat<u64, f64>, B: &SparseColMat<u64, f64>) -> SparseColMat<u64, f64> {
+    let (b_nrows, b_ncols) = (B.nrows() as u64, B.ncols());
+    let mut builder = CscBuilder::with_capacity(
+        A.nrows() * B.nrows(),
+        A.ncols() * B.ncols(),
+        A.compute_nnz() * B.compute_nnz(),
+    );
+
+    for ja in 0..A.ncols() {
+        for jb in 0..b_ncols {
+            let col = ja * b_ncols + jb;
+            for (ia, &va) in A.row_indices_of_col(ja).zip(A.values_of_col(ja)) {
+                for (ib, &vb) in B.row_indices_of_col(jb).zip(B.values_of_col(jb)) {
+                    let row = ia as u64 * b_nrows + ib as u64;
+                    builder.push(row, col, va * vb).unwrap();
+                }
             }
         }
-        SparseColMat::try_new_from_triplets(
-            reps as usize * lhs.nrows(),
-            reps as usize * lhs.ncols(),
-            &triplets,
-        )
-        .unwrap()
     }
+
+    builder.finish()
 }
 
 pub(crate) fn identity_kron2(reps: u64, A: SparseMatrix) -> SparseColMat<u64, f64> {
@@ -131,7 +172,7 @@ mod tests {
 
         assert_eq!(result.nrows(), 2);
         assert_eq!(result.ncols(), 2);
-        assert_eq!(result.compute_nnz(), 4);
+        assert_eq!(result.compute_nnz(), 3);
         assert_eq!(
             to_triplets_iter(&result).collect::<Vec<_>>(),
             vec![(0, 0, 1.0), (0, 1, 2.0), (1, 0, 3.0),]
@@ -155,13 +196,154 @@ mod tests {
                 (0, 0, 1.0),
                 (0, 1, 2.0),
                 (1, 0, 3.0),
-                (3, 3, 1.0),
-                (3, 4, 2.0),
-                (4, 3, 3.0),
-                (6, 6, 1.0),
-                (6, 7, 2.0),
-                (7, 6, 3.0),
+                (2, 2, 1.0),
+                (2, 3, 2.0),
+                (3, 2, 3.0),
+                (4, 4, 1.0),
+                (4, 5, 2.0),
+                (5, 4, 3.0),
             ]
         );
     }
+
+    #[test]
+    fn test_reshape() {
+        let mat =
+            SparseMatrix::try_new_from_triplets(2, 3, &[(0, 0, 1.0), (1, 0, 2.0), (0, 2, 3.0)])
+                .unwrap();
+
+        let result = reshape(mat.as_ref(), (3, 2));
+
+        assert_eq!(result.nrows(), 3);
+        assert_eq!(result.ncols(), 2);
+        assert_eq!(result.compute_nnz(), 3);
+        assert_eq!(
+            to_triplets_iter(&result).collect::<Vec<_>>(),
+            vec![(0, 0, 1.0), (1, 0, 2.0), (1, 1, 3.0),]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reshape_mismatched_size() {
+        let mat = SparseMatrix::try_new_from_triplets(2, 2, &[(0, 0, 1.0)]).unwrap();
+        reshape(mat.as_ref(), (3, 1));
+    }
+
+    #[test]
+    fn test_kron() {
+        let a = SparseMatrix::try_new_from_triplets(2, 2, &[(0, 0, 1.0), (1, 1, 2.0)]).unwrap();
+        let b = SparseMatrix::try_new_from_triplets(2, 1, &[(0, 0, 3.0), (1, 0, 4.0)]).unwrap();
+
+        let result = kron(&a, &b);
+
+        assert_eq!(result.nrows(), 4);
+        assert_eq!(result.ncols(), 2);
+        assert_eq!(
+            to_triplets_iter(&result).collect::<Vec<_>>(),
+            vec![(0, 0, 3.0), (1, 0, 4.0), (2, 1, 6.0), (3, 1, 8.0),]
+        );
+    }
+
+}
+
+#[cfg(test)]
+pub(crate) mod proptest_support {
+    //! `proptest` strategies for generating arbitrary [`SparseMatrix`]
+    //! instances, used to property-test the kernels in this module against
+    //! invariants instead of a handful of hardcoded examples.
+    use super::*;
+    use proptest::collection::{hash_set, vec};
+    use proptest::prelude::*;
+
+    const MAX_DIM: usize = 8;
+    const MAX_VALUE: f64 = 1e3;
+
+    /// A strategy generating `SparseMatrix` with dimensions in `1..=MAX_DIM`,
+    /// nnz bounded by `rows * cols`, distinct `(row, col)` positions, and
+    /// finite values in `[-MAX_VALUE, MAX_VALUE)`.
+    pub(crate) fn arb_sparse_matrix() -> impl Strategy<Value = SparseMatrix> {
+        (1..=MAX_DIM, 1..=MAX_DIM).prop_flat_map(|(rows, cols)| {
+            hash_set((0..rows, 0..cols), 0..=(rows * cols)).prop_flat_map(move |positions| {
+                let positions: Vec<(usize, usize)> = positions.into_iter().collect();
+                vec(-MAX_VALUE..MAX_VALUE, positions.len()).prop_map(move |values| {
+                    let triplets: Vec<(u64, u64, f64)> = positions
+                        .iter()
+                        .zip(values)
+                        .map(|(&(i, j), v)| (i as u64, j as u64, v))
+                        .collect();
+                    SparseMatrix::try_new_from_triplets(rows, cols, &triplets).unwrap()
+                })
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn identity_kron_matches_identity_kron2(reps in 1u64..4, mat in arb_sparse_matrix()) {
+            let a = identity_kron(reps, mat.clone());
+            let b = identity_kron2(reps, mat);
+            prop_assert_eq!(a.nrows(), b.nrows());
+            prop_assert_eq!(a.ncols(), b.ncols());
+            prop_assert_eq!(
+                to_triplets_iter(&a).collect::<Vec<_>>(),
+                to_triplets_iter(&b).collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn select_rows_preserves_submatrix((mat, rows) in arb_sparse_matrix().prop_flat_map(|mat| {
+            let n = mat.nrows();
+            // An arbitrary sequence of valid row indices: reordered,
+            // repeated, or omitted, not just the identity selection.
+            let rows_strategy = vec(0..n as u64, 0..3 * n);
+            (Just(mat), rows_strategy)
+        })) {
+            let selected = select_rows(&mat, &rows);
+
+            let csr = mat.to_row_major().unwrap();
+            let mut expected: Vec<(u64, u64, f64)> = rows
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &r)| {
+                    csr.col_indices_of_row(r as usize)
+                        .zip(csr.values_of_row(r as usize))
+                        .map(move |(j, &v)| (i as u64, j as u64, v))
+                })
+                .collect();
+            expected.sort_by_key(|&(i, j, _)| (j, i));
+
+            prop_assert_eq!(to_triplets_iter(&selected).collect::<Vec<_>>(), expected);
+        }
+
+        #[test]
+        fn reshape_preserves_nnz_and_values(mat in arb_sparse_matrix()) {
+            let total = mat.nrows() * mat.ncols();
+            let new_shape = factor_pair(total);
+            let reshaped = reshape(mat.as_ref(), (new_shape.0 as u64, new_shape.1 as u64));
+
+            prop_assert_eq!(reshaped.compute_nnz(), mat.compute_nnz());
+
+            let mut old_values: Vec<_> = to_triplets_iter(&mat).map(|(_, _, v)| v).collect();
+            let mut new_values: Vec<_> = to_triplets_iter(&reshaped).map(|(_, _, v)| v).collect();
+            old_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            new_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            prop_assert_eq!(old_values, new_values);
+        }
+    }
+
+    /// Picks some `(m, n)` with `m * n == total`, for use as a reshape target.
+    fn factor_pair(total: usize) -> (usize, usize) {
+        if total == 0 {
+            return (0, 1);
+        }
+        let mut m = (total as f64).sqrt() as usize;
+        while m >= 1 {
+            if total % m == 0 {
+                return (m, total / m);
+            }
+            m -= 1;
+        }
+        (1, total)
+    }
 }
\ No newline at end of file
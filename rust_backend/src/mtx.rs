@@ -0,0 +1,169 @@
+//! Read and write [`SparseMatrix`] in the MatrixMarket coordinate text format.
+//!
+//! Only the `real general` coordinate variant is supported, which is the
+//! subset needed to dump and reload the problem-data matrices produced by
+//! `View::get_tensor_representation` for inspection and testing.
+
+use std::io::{self, BufRead, Write};
+
+use crate::SparseMatrix;
+
+const BANNER: &str = "%%MatrixMarket matrix coordinate real general";
+
+/// Write `a` to `w` in MatrixMarket coordinate format, using 1-based indices.
+pub fn write<W: Write>(a: &SparseMatrix, mut w: W) -> io::Result<()> {
+    writeln!(w, "{BANNER}")?;
+    writeln!(w, "{} {} {}", a.nrows(), a.ncols(), a.compute_nnz())?;
+    for (i, j, v) in crate::faer_ext::to_triplets_iter(a) {
+        writeln!(w, "{} {} {}", i + 1, j + 1, v)?;
+    }
+    Ok(())
+}
+
+/// Read a matrix from `r` in MatrixMarket coordinate format.
+///
+/// The banner line is checked, `%` comment lines are skipped, and the
+/// `rows cols nnz` shape line is followed by `nnz` `i j value` entries with
+/// 1-based indices.
+pub fn read<R: BufRead>(r: R) -> io::Result<SparseMatrix> {
+    let mut lines = r.lines();
+
+    let banner = lines
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "missing MatrixMarket banner"))??;
+    if banner.trim() != BANNER {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported MatrixMarket banner: {banner}"),
+        ));
+    }
+
+    let mut shape_line = None;
+    for line in lines.by_ref() {
+        let line = line?;
+        if line.starts_with('%') {
+            continue;
+        }
+        shape_line = Some(line);
+        break;
+    }
+    let shape_line = shape_line
+        .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "missing shape line"))?;
+    let mut shape = shape_line.split_whitespace();
+    let rows: usize = parse_field(shape.next(), "rows")?;
+    let cols: usize = parse_field(shape.next(), "cols")?;
+    let nnz: usize = parse_field(shape.next(), "nnz")?;
+
+    // `nnz` comes straight from the file and may be corrupted or hand-edited
+    // to an enormous value; cap the capacity hint so a bogus shape line
+    // reports a parse error instead of aborting the process on an
+    // allocation panic. The real count is still checked against `nnz` below.
+    const MAX_PREALLOC_NNZ: usize = 1 << 20;
+    let mut triplets = Vec::with_capacity(nnz.min(MAX_PREALLOC_NNZ));
+    for line in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let i: u64 = parse_field(fields.next(), "row index")?;
+        let j: u64 = parse_field(fields.next(), "col index")?;
+        let v: f64 = parse_field(fields.next(), "value")?;
+        let i = i.checked_sub(1).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "row index must be 1-based (>= 1)")
+        })?;
+        let j = j.checked_sub(1).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "col index must be 1-based (>= 1)")
+        })?;
+        triplets.push((i, j, v));
+    }
+
+    if triplets.len() != nnz {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "declared nnz {nnz} does not match the {} entries actually read",
+                triplets.len()
+            ),
+        ));
+    }
+
+    SparseMatrix::try_new_from_triplets(rows, cols, &triplets)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{e:?}")))
+}
+
+fn parse_field<T: std::str::FromStr>(field: Option<&str>, name: &str) -> io::Result<T> {
+    field
+        .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, format!("missing {name}")))?
+        .parse()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("invalid {name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mat = SparseMatrix::try_new_from_triplets(
+            2,
+            3,
+            &[(0, 0, 1.0), (1, 0, 2.0), (0, 2, 3.5)],
+        )
+        .unwrap();
+
+        let mut buf = Vec::new();
+        write(&mat, &mut buf).unwrap();
+
+        let round_tripped = read(buf.as_slice()).unwrap();
+        assert_eq!(round_tripped.nrows(), mat.nrows());
+        assert_eq!(round_tripped.ncols(), mat.ncols());
+        assert_eq!(
+            crate::faer_ext::to_triplets_iter(&round_tripped).collect::<Vec<_>>(),
+            crate::faer_ext::to_triplets_iter(&mat).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_read_skips_comments() {
+        let input = "%%MatrixMarket matrix coordinate real general\n\
+                     % a comment\n\
+                     2 2 1\n\
+                     1 1 4.0\n";
+        let mat = read(input.as_bytes()).unwrap();
+        assert_eq!(mat.nrows(), 2);
+        assert_eq!(mat.ncols(), 2);
+        assert_eq!(
+            crate::faer_ext::to_triplets_iter(&mat).collect::<Vec<_>>(),
+            vec![(0, 0, 4.0)]
+        );
+    }
+
+    #[test]
+    fn test_read_rejects_zero_index() {
+        let input = "%%MatrixMarket matrix coordinate real general\n\
+                     2 2 1\n\
+                     0 1 4.0\n";
+        let err = read(input.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_read_rejects_nnz_mismatch_too_few() {
+        let input = "%%MatrixMarket matrix coordinate real general\n\
+                     2 2 2\n\
+                     1 1 4.0\n";
+        let err = read(input.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_read_rejects_nnz_mismatch_too_many() {
+        let input = "%%MatrixMarket matrix coordinate real general\n\
+                     2 2 1\n\
+                     1 1 4.0\n\
+                     2 2 5.0\n";
+        let err = read(input.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
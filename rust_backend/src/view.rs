@@ -119,7 +119,7 @@ impl<'a> View<'a> {
                         new_rows.push(r + m * i);
                     }
                 }
-                faer_ext::select_rows(x, rows)
+                faer_ext::select_rows(x, &new_rows)
             }
         };
 
@@ -141,24 +141,77 @@ impl<'a> View<'a> {
         func: impl Fn(&SparseColMat<u64, f64>, u64) -> SparseColMat<u64, f64>,
         is_parameter_free_function: bool,
     ) -> Self {
-        for (variable_id, tensor) in &self.tensor {
-            self.tensor[variable_id] = if is_parameter_free_function {
-                self.apply_to_parameters(func, tensor)
-            } else {
-                // func(&tensor[&CONST_ID], 1)
-                todo!("Implement accumulate_over_variables")
-            };
-        }
+        self.tensor = self
+            .tensor
+            .iter()
+            .map(|(&variable_id, tensor)| {
+                let new_tensor = if is_parameter_free_function {
+                    self.apply_to_parameters(&func, tensor)
+                } else {
+                    self.fold_over_parameters(&func, tensor)
+                };
+                (variable_id, new_tensor)
+            })
+            .collect();
 
-        let is_parameter_free = self.is_parameter_free && is_parameter_free_function;
+        self.is_parameter_free = self.is_parameter_free && is_parameter_free_function;
         self
     }
 
+    /// Maps `func` over each `(param_id -> matrix)` entry of `tensor`,
+    /// passing the number of slices stacked in that parameter's matrix
+    /// (`context.param_to_size[&param_id]`) as the second argument, the
+    /// same convention `apply_all` uses for parameter-free functions.
     pub(crate) fn apply_to_parameters(
         &self,
         func: impl Fn(&SparseColMat<u64, f64>, u64) -> SparseColMat<u64, f64>,
         tensor: &HashMap<i64, SparseColMat<u64, f64>>,
     ) -> HashMap<i64, SparseColMat<u64, f64>> {
-        todo!("Implement apply_to_parameters")
+        tensor
+            .iter()
+            .map(|(&param_id, matrix)| {
+                let p = self.context.param_to_size[&param_id] as u64;
+                (param_id, func(matrix, p))
+            })
+            .collect()
+    }
+
+    /// Applies a function that is not parameter-free across a variable's
+    /// full parameter map.
+    ///
+    /// Such a function cannot be applied to each parameter's matrix
+    /// independently, so every parameter's slices are stacked on top of
+    /// each other (in increasing parameter id order) into a single matrix,
+    /// `func` is applied once to that combined matrix with the total
+    /// number of stacked slices, and the single result is stored under
+    /// `CONST_ID`, the only parameter id left once the parameters have
+    /// been folded together.
+    fn fold_over_parameters(
+        &self,
+        func: impl Fn(&SparseColMat<u64, f64>, u64) -> SparseColMat<u64, f64>,
+        tensor: &HashMap<i64, SparseColMat<u64, f64>>,
+    ) -> HashMap<i64, SparseColMat<u64, f64>> {
+        let mut param_ids: Vec<i64> = tensor.keys().copied().collect();
+        param_ids.sort_unstable();
+
+        let ncols = tensor[&param_ids[0]].ncols();
+        let total_p: i64 = param_ids
+            .iter()
+            .map(|param_id| self.context.param_to_size[param_id])
+            .sum();
+
+        let mut triplets = Vec::new();
+        let mut row_offset = 0u64;
+        for &param_id in &param_ids {
+            let matrix = &tensor[&param_id];
+            for (i, j, v) in to_triplets_iter(matrix) {
+                triplets.push((i + row_offset, j, v));
+            }
+            row_offset += matrix.nrows() as u64;
+        }
+        let stacked =
+            SparseColMat::try_new_from_triplets(row_offset as usize, ncols, &triplets).unwrap();
+
+        HashMap::from([(CONST_ID, func(&stacked, total_p as u64))])
     }
 }
\ No newline at end of file